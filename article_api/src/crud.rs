@@ -0,0 +1,26 @@
+//! Proof that `crud_resource!` covers the UUID-keyed case: the plain
+//! create/get/list/update/soft-delete surface for articles, generated
+//! instead of hand-written. Mounted under `/articles` alongside the
+//! existing `/post/...` routes, which stay hand-written because they carry
+//! behavior the macro doesn't model (pagination, full-text search,
+//! rendering, federation).
+use crate::errors::AppError;
+use crate::models::{Article, CreateArticle, UpdateArticle};
+use crate::schema::articles::dsl::*;
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use diesel::r2d2::{self, ConnectionManager};
+use diesel::PgConnection;
+use uuid::Uuid;
+
+type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
+
+crud_macros::crud_resource! {
+    articles => Article {
+        id: Uuid,
+        scope: "/articles",
+        create: CreateArticle,
+        update: UpdateArticle,
+        soft_delete: (is_deleted, deleted_at),
+    }
+}
@@ -0,0 +1,178 @@
+use crate::errors::AppError;
+use crate::models::Article;
+use crate::schema::articles;
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use diesel::r2d2::{self, ConnectionManager};
+use diesel::PgConnection;
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
+
+const ACTOR_KEY_PATH: &str = "actor_key.pem";
+const BASE_URL_ENV: &str = "FEDERATION_BASE_URL";
+const DEFAULT_BASE_URL: &str = "http://localhost:8080";
+
+/// The RSA keypair used to sign outgoing ActivityPub activities
+/// (HTTP Signatures). Generated once and persisted to disk so the actor's
+/// `publicKeyPem` stays stable across restarts.
+pub struct ActorKey {
+    pub private_key: RsaPrivateKey,
+    pub public_key_pem: String,
+}
+
+impl ActorKey {
+    pub fn load_or_generate() -> Self {
+        let private_key = if Path::new(ACTOR_KEY_PATH).exists() {
+            let pem = fs::read_to_string(ACTOR_KEY_PATH).expect("failed to read actor key");
+            RsaPrivateKey::from_pkcs8_pem(&pem).expect("failed to parse actor key")
+        } else {
+            let mut rng = rand::thread_rng();
+            let private_key =
+                RsaPrivateKey::new(&mut rng, 2048).expect("failed to generate RSA keypair");
+            let pem = private_key
+                .to_pkcs8_pem(LineEnding::LF)
+                .expect("failed to encode actor key");
+            fs::write(ACTOR_KEY_PATH, pem.as_str()).expect("failed to persist actor key");
+            private_key
+        };
+
+        let public_key_pem = RsaPublicKey::from(&private_key)
+            .to_public_key_pem(LineEnding::LF)
+            .expect("failed to encode actor public key");
+
+        ActorKey {
+            private_key,
+            public_key_pem,
+        }
+    }
+}
+
+fn base_url() -> String {
+    std::env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.route("/actor", web::get().to(get_actor))
+        .route("/outbox", web::get().to(get_outbox))
+        .route("/objects/{uuid}", web::get().to(get_object));
+}
+
+async fn get_actor(key: web::Data<ActorKey>) -> HttpResponse {
+    let base = base_url();
+    let actor = json!({
+        "@context": ["https://www.w3.org/ns/activitystreams", "https://w3id.org/security/v1"],
+        "id": format!("{}/actor", base),
+        "type": "Person",
+        "preferredUsername": "blog",
+        "inbox": format!("{}/inbox", base),
+        "outbox": format!("{}/outbox", base),
+        "publicKey": {
+            "id": format!("{}/actor#main-key", base),
+            "owner": format!("{}/actor", base),
+            "publicKeyPem": key.public_key_pem,
+        },
+    });
+
+    HttpResponse::Ok()
+        .content_type("application/activity+json")
+        .json(actor)
+}
+
+async fn get_outbox(pool: web::Data<DbPool>) -> Result<HttpResponse, AppError> {
+    let published = web::block(move || {
+        let mut conn = pool.get()?;
+        articles::table
+            .filter(articles::is_published.eq(true))
+            .filter(articles::is_deleted.eq(false))
+            .order(articles::published_at.desc())
+            .load::<Article>(&mut conn)
+            .map_err(AppError::from)
+    })
+    .await??;
+
+    let base = base_url();
+    let items: Vec<Value> = published
+        .iter()
+        .map(|article| create_activity(&base, article))
+        .collect();
+
+    let collection = json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/outbox", base),
+        "type": "OrderedCollection",
+        "totalItems": items.len(),
+        "orderedItems": items,
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/activity+json")
+        .json(collection))
+}
+
+async fn get_object(
+    pool: web::Data<DbPool>,
+    article_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let article_id = article_id.into_inner();
+
+    let article = web::block(move || {
+        let mut conn = pool.get()?;
+        articles::table
+            .filter(articles::id.eq(article_id))
+            .filter(articles::is_published.eq(true))
+            .filter(articles::is_deleted.eq(false))
+            .first::<Article>(&mut conn)
+            .map_err(AppError::from)
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/activity+json")
+        .json(note_object(&base_url(), &article)))
+}
+
+fn note_object(base: &str, article: &Article) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/objects/{}", base, article.id),
+        "type": "Article",
+        "attributedTo": format!("{}/actor", base),
+        "name": article.title,
+        "content": article.content,
+        "published": article.published_at.and_utc().to_rfc3339(),
+    })
+}
+
+fn create_activity(base: &str, article: &Article) -> Value {
+    json!({
+        "id": format!("{}/objects/{}/activity", base, article.id),
+        "type": "Create",
+        "actor": format!("{}/actor", base),
+        "published": article.published_at.and_utc().to_rfc3339(),
+        "to": ["https://www.w3.org/ns/activitystreams#Public"],
+        "object": note_object(base, article),
+    })
+}
+
+/// Builds the `Delete` activity shape for a soft-deleted article. There is
+/// no follower/inbox table yet to deliver it to, so for now this just logs
+/// the activity that a follow-up delivery worker would federate out.
+pub fn delete_activity(article_id: Uuid) -> Value {
+    let base = base_url();
+    let activity = json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/objects/{}/delete", base, article_id),
+        "type": "Delete",
+        "actor": format!("{}/actor", base),
+        "to": ["https://www.w3.org/ns/activitystreams#Public"],
+        "object": format!("{}/objects/{}", base, article_id),
+    });
+    log::info!("Federating delete activity: {}", activity);
+    activity
+}
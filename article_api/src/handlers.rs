@@ -1,19 +1,98 @@
-use crate::models::{Article, CreateArticle, UpdateArticle};
+use crate::errors::AppError;
+use crate::models::{Article, CreateArticle, RenderedArticle, UpdateArticle};
+use crate::query::{ListQuery, Page};
+use crate::rendering::RenderCache;
+use crate::schema::articles;
 use crate::schema::articles::dsl::*;
 use actix_web::{web, HttpResponse};
-use chrono::{NaiveDateTime, Utc};
+use chrono::Utc;
+use diesel::dsl::sql;
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use diesel::r2d2::{self, ConnectionManager};
+use diesel::sql_types::{Bool, Float, Text};
 use diesel::PgConnection;
 use uuid::Uuid;
 
 type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
+type BoxedArticlesQuery<'a> = articles::BoxedQuery<'a, Pg>;
+
+/// Applies the free-text `q` filter as a Postgres full-text search over
+/// title + content. Only filters; relevance ranking is `apply_ordering`'s job.
+fn apply_search(query: BoxedArticlesQuery<'_>, q: Option<&str>) -> BoxedArticlesQuery<'_> {
+    match q {
+        Some(term) if !term.is_empty() => query.filter(
+            sql::<Bool>("to_tsvector('english', title || ' ' || content) @@ plainto_tsquery(")
+                .bind::<Text, _>(term.to_string())
+                .sql(")"),
+        ),
+        _ => query,
+    }
+}
+
+/// Applies the `sort` column/direction (already validated against the
+/// sortable-column allowlist) to a boxed query.
+fn apply_sort(query: BoxedArticlesQuery<'_>, list_query: &ListQuery) -> BoxedArticlesQuery<'_> {
+    let descending = list_query.sort_descending();
+    match list_query.sort_column() {
+        Some("title") if descending => query.order(title.desc()),
+        Some("title") => query.order(title.asc()),
+        Some("is_published") if descending => query.order(is_published.desc()),
+        Some("is_published") => query.order(is_published.asc()),
+        Some("published_at") if descending => query.order(published_at.desc()),
+        Some("published_at") => query.order(published_at.asc()),
+        None => query.order(published_at.desc()),
+        Some(_) => query.order(published_at.desc()),
+    }
+}
+
+/// Orders a boxed query. A free-text `q` wins over an explicit `sort`: its
+/// matches are ranked by `ts_rank` relevance (best match first) rather than
+/// by the requested column, since that's what "ranked by relevance" means
+/// for a search result page. Without `q`, falls back to `apply_sort`.
+fn apply_ordering(query: BoxedArticlesQuery<'_>, list_query: &ListQuery) -> BoxedArticlesQuery<'_> {
+    match list_query.q.as_deref() {
+        Some(term) if !term.is_empty() => query.order(
+            sql::<Float>("ts_rank(to_tsvector('english', title || ' ' || content), plainto_tsquery(")
+                .bind::<Text, _>(term.to_string())
+                .sql(")) DESC"),
+        ),
+        _ => apply_sort(query, list_query),
+    }
+}
+
+/// Runs the shared count-then-page query pattern behind every listing
+/// endpoint: `base` builds the resource-specific filter (e.g. published vs.
+/// deleted) as a fresh boxed query each time it's called, since a boxed
+/// query is consumed by `.count()`/`.load()` and can't be reused.
+fn fetch_page(
+    conn: &mut PgConnection,
+    base: impl Fn() -> BoxedArticlesQuery<'static>,
+    list_query: &ListQuery,
+) -> Result<Page<Article>, AppError> {
+    let total = apply_search(base(), list_query.q.as_deref())
+        .count()
+        .get_result::<i64>(conn)?;
+
+    let items = apply_ordering(apply_search(base(), list_query.q.as_deref()), list_query)
+        .limit(list_query.limit)
+        .offset(list_query.offset)
+        .load::<Article>(conn)?;
+
+    Ok(Page {
+        total,
+        limit: list_query.limit,
+        offset: list_query.offset,
+        items,
+    })
+}
 
 pub fn init(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/post")
             .route("/create_post", web::post().to(create_post))
             .route("/get_post/{uuid}", web::get().to(get_post))
+            .route("/get_post/{uuid}/rendered", web::get().to(get_post_rendered))
             .route("/list_posts", web::get().to(list_posts))
             .route("/list_all_posts", web::get().to(list_all_posts))
             .route("/list_deleted_posts", web::get().to(list_deleted_posts))
@@ -23,7 +102,10 @@ pub fn init(cfg: &mut web::ServiceConfig) {
     );
 }
 
-async fn create_post(pool: web::Data<DbPool>, item: web::Json<CreateArticle>) -> HttpResponse {
+async fn create_post(
+    pool: web::Data<DbPool>,
+    item: web::Json<CreateArticle>,
+) -> Result<HttpResponse, AppError> {
     let new_article = Article {
         id: Uuid::new_v4(),
         title: item.title.clone(),
@@ -34,108 +116,194 @@ async fn create_post(pool: web::Data<DbPool>, item: web::Json<CreateArticle>) ->
         deleted_at: None,
     };
 
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
+    let new_id = web::block(move || {
+        let mut conn = pool.get()?;
+        diesel::insert_into(articles)
+            .values(&new_article)
+            .execute(&mut conn)?;
+        Ok::<_, AppError>(new_article.id)
+    })
+    .await??;
 
-    diesel::insert_into(articles)
-        .values(&new_article)
-        .execute(&mut conn)
-        .expect("Error saving new post");
+    Ok(HttpResponse::Ok().json(new_id))
+}
+
+async fn get_post(
+    pool: web::Data<DbPool>,
+    article_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let article_id = article_id.into_inner();
 
-    HttpResponse::Ok().json(new_article.id)
+    let article = web::block(move || {
+        let mut conn = pool.get()?;
+        articles
+            .filter(id.eq(article_id))
+            .first::<Article>(&mut conn)
+            .map_err(AppError::from)
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(article))
 }
 
-async fn get_post(pool: web::Data<DbPool>, article_id: web::Path<Uuid>) -> HttpResponse {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-    let result = articles
-        .filter(id.eq(article_id.into_inner()))
-        .first::<Article>(&mut conn)
-        .optional()
-        .expect("Error loading post");
-
-    match result {
-        Some(article) => HttpResponse::Ok().json(article),
-        None => HttpResponse::NotFound().finish(),
-    }
+async fn get_post_rendered(
+    pool: web::Data<DbPool>,
+    cache: web::Data<RenderCache>,
+    article_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let article_id = article_id.into_inner();
+
+    let article = web::block(move || {
+        let mut conn = pool.get()?;
+        articles
+            .filter(id.eq(article_id))
+            .first::<Article>(&mut conn)
+            .map_err(AppError::from)
+    })
+    .await??;
+
+    let content_html = cache.get_or_render(&article);
+
+    Ok(HttpResponse::Ok().json(RenderedArticle {
+        article,
+        content_html,
+    }))
 }
 
-async fn list_posts(pool: web::Data<DbPool>) -> HttpResponse {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-    let result = articles
-        .filter(is_deleted.eq(false))
-        .filter(is_published.eq(true))
-        .load::<Article>(&mut conn)
-        .expect("Error loading posts");
+async fn list_posts(
+    pool: web::Data<DbPool>,
+    list_query: web::Query<ListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let list_query = list_query.into_inner();
+    list_query.validate()?;
+
+    let page = web::block(move || {
+        let mut conn = pool.get()?;
+        fetch_page(
+            &mut conn,
+            || {
+                articles
+                    .filter(is_deleted.eq(false))
+                    .filter(is_published.eq(true))
+                    .into_boxed()
+            },
+            &list_query,
+        )
+    })
+    .await??;
 
-    HttpResponse::Ok().json(result)
+    Ok(HttpResponse::Ok().json(page))
 }
 
-async fn list_all_posts(pool: web::Data<DbPool>) -> HttpResponse {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-    let result = articles
-        .filter(is_deleted.eq(false))
-        .load::<Article>(&mut conn)
-        .expect("Error loading posts");
+async fn list_all_posts(
+    pool: web::Data<DbPool>,
+    list_query: web::Query<ListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let list_query = list_query.into_inner();
+    list_query.validate()?;
+
+    let page = web::block(move || {
+        let mut conn = pool.get()?;
+        fetch_page(
+            &mut conn,
+            || articles.filter(is_deleted.eq(false)).into_boxed(),
+            &list_query,
+        )
+    })
+    .await??;
 
-    HttpResponse::Ok().json(result)
+    Ok(HttpResponse::Ok().json(page))
 }
 
-async fn list_deleted_posts(pool: web::Data<DbPool>) -> HttpResponse {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-    let result = articles
-        .filter(is_deleted.eq(true))
-        .load::<Article>(&mut conn)
-        .expect("Error loading posts");
+async fn list_deleted_posts(
+    pool: web::Data<DbPool>,
+    list_query: web::Query<ListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let list_query = list_query.into_inner();
+    list_query.validate()?;
+
+    let page = web::block(move || {
+        let mut conn = pool.get()?;
+        fetch_page(
+            &mut conn,
+            || articles.filter(is_deleted.eq(true)).into_boxed(),
+            &list_query,
+        )
+    })
+    .await??;
 
-    HttpResponse::Ok().json(result)
+    Ok(HttpResponse::Ok().json(page))
 }
 
 async fn update_post(
     pool: web::Data<DbPool>,
+    cache: web::Data<RenderCache>,
     article_id: web::Path<Uuid>,
     item: web::Json<UpdateArticle>,
-) -> HttpResponse {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
+) -> Result<HttpResponse, AppError> {
+    let article_id = article_id.into_inner();
+    let item = item.into_inner();
 
-    let updated = diesel::update(articles.filter(id.eq(article_id.into_inner())))
-        .set(&*item)
-        .execute(&mut conn)
-        .expect("Error updating post");
+    let updated = web::block(move || {
+        let mut conn = pool.get()?;
+        diesel::update(articles.filter(id.eq(article_id)))
+            .set(&item)
+            .execute(&mut conn)
+            .map_err(AppError::from)
+    })
+    .await??;
 
     if updated > 0 {
-        HttpResponse::Ok().finish()
+        cache.invalidate(article_id);
+        Ok(HttpResponse::Ok().finish())
     } else {
-        HttpResponse::NotFound().finish()
+        Err(AppError::NotFound)
     }
 }
 
-async fn delete_post(pool: web::Data<DbPool>, article_id: web::Path<Uuid>) -> HttpResponse {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
+async fn delete_post(
+    pool: web::Data<DbPool>,
+    article_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let article_id = article_id.into_inner();
 
-    let deleted = diesel::delete(articles.filter(id.eq(article_id.into_inner())))
-        .execute(&mut conn)
-        .expect("Error deleting post");
+    let deleted = web::block(move || {
+        let mut conn = pool.get()?;
+        diesel::delete(articles.filter(id.eq(article_id)))
+            .execute(&mut conn)
+            .map_err(AppError::from)
+    })
+    .await??;
 
     if deleted > 0 {
-        HttpResponse::Ok().finish()
+        Ok(HttpResponse::Ok().finish())
     } else {
-        HttpResponse::NotFound().finish()
+        Err(AppError::NotFound)
     }
 }
 
-async fn remove_post(pool: web::Data<DbPool>, article_id: web::Path<Uuid>) -> HttpResponse {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
+async fn remove_post(
+    pool: web::Data<DbPool>,
+    article_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let article_id = article_id.into_inner();
 
-    let updated = diesel::update(articles.filter(id.eq(article_id.into_inner())))
-        .set((
-            is_deleted.eq(true),
-            deleted_at.eq(Some(Utc::now().naive_utc())),
-        ))
-        .execute(&mut conn)
-        .expect("Error marking post as deleted");
+    let updated = web::block(move || {
+        let mut conn = pool.get()?;
+        diesel::update(articles.filter(id.eq(article_id)))
+            .set((
+                is_deleted.eq(true),
+                deleted_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .execute(&mut conn)
+            .map_err(AppError::from)
+    })
+    .await??;
 
     if updated > 0 {
-        HttpResponse::Ok().finish()
+        crate::federation::delete_activity(article_id);
+        Ok(HttpResponse::Ok().finish())
     } else {
-        HttpResponse::NotFound().finish()
+        Err(AppError::NotFound)
     }
 }
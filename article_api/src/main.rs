@@ -4,10 +4,19 @@ use diesel::PgConnection;
 use std::env;
 use dotenv::dotenv;
 
+pub mod crud;
+pub mod errors;
+pub mod federation;
 pub mod handlers;
+pub mod migrations;
 pub mod models;
+pub mod query;
+pub mod rendering;
 pub mod schema;
 
+use federation::ActorKey;
+use rendering::RenderCache;
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
@@ -19,10 +28,25 @@ async fn main() -> std::io::Result<()> {
         .build(manager)
         .expect("Fail to create database pool.");
 
+    let mut conn = pool.get().expect("couldn't get db connection from pool");
+    if env::args().any(|arg| arg == "--revert") {
+        migrations::revert_last(&mut conn);
+        return Ok(());
+    }
+    migrations::run_pending(&mut conn);
+    drop(conn);
+
+    let render_cache = web::Data::new(RenderCache::new());
+    let actor_key = web::Data::new(ActorKey::load_or_generate());
+
     HttpServer::new(move || {
         App::new()
         .app_data(web::Data::new(pool.clone()))
+            .app_data(render_cache.clone())
+            .app_data(actor_key.clone())
             .configure(handlers::init)
+            .configure(federation::init)
+            .configure(crud::articles_routes)
     })
         .bind("127.0.0.1:8080")?
     .run()
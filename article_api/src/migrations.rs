@@ -0,0 +1,30 @@
+use diesel::pg::PgConnection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// Applies every migration under `migrations/` that has not yet been
+/// recorded in `__diesel_schema_migrations`, each inside its own
+/// transaction, and logs the version of each one it runs.
+pub fn run_pending(conn: &mut PgConnection) {
+    let applied = conn
+        .run_pending_migrations(MIGRATIONS)
+        .expect("Failed to run pending migrations");
+
+    if applied.is_empty() {
+        log::info!("No pending migrations to run.");
+    }
+    for migration in applied {
+        log::info!("Applied migration {}", migration);
+    }
+}
+
+/// Reverts the most recently applied migration by running its `down.sql`.
+/// Backs the `--revert` startup flag.
+pub fn revert_last(conn: &mut PgConnection) {
+    let reverted = conn
+        .revert_last_migration(MIGRATIONS)
+        .expect("Failed to revert last migration");
+
+    log::info!("Reverted migration {}", reverted);
+}
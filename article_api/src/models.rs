@@ -16,7 +16,8 @@ pub struct Article {
     pub deleted_at: Option<NaiveDateTime>,
 }
 
-#[derive(Deserialize)]
+#[derive(Insertable, Deserialize)]
+#[table_name = "articles"]
 pub struct CreateArticle {
     pub title: String,
     pub content: String,
@@ -31,3 +32,12 @@ pub struct UpdateArticle {
     pub is_published: Option<bool>,
     pub published_at: Option<NaiveDateTime>,
 }
+
+/// An article alongside its Markdown content rendered to sanitized,
+/// syntax-highlighted HTML.
+#[derive(Serialize)]
+pub struct RenderedArticle {
+    #[serde(flatten)]
+    pub article: Article,
+    pub content_html: String,
+}
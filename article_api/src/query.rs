@@ -0,0 +1,75 @@
+use crate::errors::AppError;
+use serde::{Deserialize, Serialize};
+
+/// Hard ceiling on `limit` so a listing endpoint can't be used to pull the
+/// whole table into memory in one request.
+pub const MAX_LIMIT: i64 = 100;
+const DEFAULT_LIMIT: i64 = 20;
+
+/// Columns callers are allowed to sort by. Keeping this an allowlist (rather
+/// than trusting the query string column name directly) keeps `sort` from
+/// becoming a vector for arbitrary SQL.
+const SORTABLE_COLUMNS: &[&str] = &["published_at", "title", "is_published"];
+
+#[derive(Debug, Deserialize)]
+pub struct ListQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    pub sort: Option<String>,
+    pub q: Option<String>,
+}
+
+fn default_limit() -> i64 {
+    DEFAULT_LIMIT
+}
+
+impl ListQuery {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.limit <= 0 || self.limit > MAX_LIMIT {
+            return Err(AppError::Validation(format!(
+                "limit must be between 1 and {}",
+                MAX_LIMIT
+            )));
+        }
+        if self.offset < 0 {
+            return Err(AppError::Validation("offset must not be negative".to_string()));
+        }
+        if let Some(column) = self.sort_column() {
+            if !SORTABLE_COLUMNS.contains(&column) {
+                return Err(AppError::Validation(format!(
+                    "cannot sort by '{}', expected one of {:?}",
+                    column, SORTABLE_COLUMNS
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The column portion of `sort`, e.g. `"published_at"` out of `"published_at:desc"`.
+    pub fn sort_column(&self) -> Option<&str> {
+        self.sort
+            .as_deref()
+            .map(|value| value.split(':').next().unwrap_or(value))
+    }
+
+    /// Whether `sort` asked for a descending order, e.g. `"published_at:desc"`.
+    pub fn sort_descending(&self) -> bool {
+        self.sort
+            .as_deref()
+            .and_then(|value| value.split(':').nth(1))
+            .map(|direction| direction.eq_ignore_ascii_case("desc"))
+            .unwrap_or(false)
+    }
+}
+
+/// Envelope returned by the listing endpoints: the page of rows plus the
+/// total row count so clients can render pagination controls.
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<T>,
+}
@@ -0,0 +1,129 @@
+use crate::models::Article;
+use once_cell::sync::Lazy;
+use pulldown_cmark::{html, CodeBlockKind, Event, Parser, Tag};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{styled_line_to_highlighted_html, IncludeBackground};
+use syntect::parsing::SyntaxSet;
+use uuid::Uuid;
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+const THEME: &str = "InspiredGitHub";
+
+/// Renders stored article content as Markdown, emitting syntax-highlighted
+/// HTML spans for each fenced code block, then runs the result through an
+/// HTML sanitizer so raw `<script>`/event-handler markup in stored content
+/// can't reach a reader's browser.
+pub fn render_markdown(source: &str) -> String {
+    let parser = Parser::new(source);
+    let mut events = Vec::new();
+    let mut code_buffer = String::new();
+    let mut current_lang = None;
+    let mut in_code_block = false;
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
+                in_code_block = true;
+                current_lang = Some(lang.to_string());
+                code_buffer.clear();
+            }
+            Event::Text(text) if in_code_block => code_buffer.push_str(&text),
+            Event::End(Tag::CodeBlock(_)) if in_code_block => {
+                in_code_block = false;
+                let highlighted = highlight_code_block(&code_buffer, current_lang.take().as_deref());
+                events.push(Event::Html(highlighted.into()));
+            }
+            other => events.push(other),
+        }
+    }
+
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, events.into_iter());
+    sanitize(&rendered)
+}
+
+/// Strips anything that isn't in the sanitizer's tag/attribute allowlist
+/// (scripts, event handlers, inline `javascript:` links, ...), while still
+/// allowing the `span`/`style` markup `highlight_code_block` emits.
+fn sanitize(raw_html: &str) -> String {
+    ammonia::Builder::default()
+        .add_tags(["span"])
+        .add_generic_attributes(["style", "class"])
+        .clean(raw_html)
+        .to_string()
+}
+
+fn highlight_code_block(code: &str, lang: Option<&str>) -> String {
+    let syntax = lang
+        .and_then(|token| SYNTAX_SET.find_syntax_by_token(token))
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+    let theme = &THEME_SET.themes[THEME];
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let mut block = String::from("<pre><code>");
+    for line in code.lines() {
+        let ranges = highlighter
+            .highlight_line(line, &SYNTAX_SET)
+            .unwrap_or_default();
+        if let Ok(line_html) = styled_line_to_highlighted_html(&ranges[..], IncludeBackground::No) {
+            block.push_str(&line_html);
+        }
+        block.push('\n');
+    }
+    block.push_str("</code></pre>");
+    block
+}
+
+fn hash_content(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Caches rendered HTML per article, keyed by a hash of the article's
+/// `content`. Keying on the content itself (rather than `published_at`)
+/// means any write that changes `content` — whether through `update_post`
+/// or the macro-generated `/articles/update/{id}` route — is automatically
+/// a cache miss, with no separate invalidation call required.
+pub struct RenderCache {
+    entries: Mutex<HashMap<Uuid, (u64, String)>>,
+}
+
+impl RenderCache {
+    pub fn new() -> Self {
+        RenderCache {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get_or_render(&self, article: &Article) -> String {
+        let content_hash = hash_content(&article.content);
+
+        let mut entries = self.entries.lock().unwrap();
+        if let Some((cached_hash, html)) = entries.get(&article.id) {
+            if *cached_hash == content_hash {
+                return html.clone();
+            }
+        }
+
+        let html = render_markdown(&article.content);
+        entries.insert(article.id, (content_hash, html.clone()));
+        html
+    }
+
+    pub fn invalidate(&self, article_id: Uuid) {
+        self.entries.lock().unwrap().remove(&article_id);
+    }
+}
+
+impl Default for RenderCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,289 @@
+//! `crud_resource!` expands a `table => Model { ... }` spec into the usual
+//! create/get/list/update/remove Actix handlers plus a `ServiceConfig`
+//! registration function, so adding a new Diesel-backed resource no longer
+//! means copying the posts or events handler module by hand.
+//!
+//! The calling module is expected to already have `diesel::prelude::*`,
+//! the table's `dsl::*`, a local `AppError` and a `DbPool` type alias in
+//! scope, matching the pattern the posts and events handlers use.
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, parenthesized, parse_macro_input, Ident, LitStr, Token, Type};
+
+struct SoftDelete {
+    flag_column: Ident,
+    timestamp_column: Ident,
+}
+
+struct CrudResource {
+    table: Ident,
+    model: Ident,
+    pk_name: Ident,
+    pk_ty: Type,
+    scope: Option<LitStr>,
+    create_ty: Ident,
+    update_ty: Ident,
+    soft_delete: Option<SoftDelete>,
+}
+
+impl Parse for CrudResource {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let table: Ident = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let model: Ident = input.parse()?;
+
+        let content;
+        braced!(content in input);
+
+        let mut pk = None;
+        let mut scope = None;
+        let mut create_ty = None;
+        let mut update_ty = None;
+        let mut soft_delete = None;
+
+        while !content.is_empty() {
+            let key: Ident = content.parse()?;
+            content.parse::<Token![:]>()?;
+
+            if key == "create" {
+                create_ty = Some(content.parse::<Ident>()?);
+            } else if key == "update" {
+                update_ty = Some(content.parse::<Ident>()?);
+            } else if key == "scope" {
+                scope = Some(content.parse::<LitStr>()?);
+            } else if key == "soft_delete" {
+                let inner;
+                parenthesized!(inner in content);
+                let flag_column: Ident = inner.parse()?;
+                inner.parse::<Token![,]>()?;
+                let timestamp_column: Ident = inner.parse()?;
+                soft_delete = Some(SoftDelete {
+                    flag_column,
+                    timestamp_column,
+                });
+            } else {
+                // Anything else is the primary-key field, e.g. `id: Uuid`.
+                let ty: Type = content.parse()?;
+                pk = Some((key, ty));
+            }
+
+            if content.peek(Token![,]) {
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        let (pk_name, pk_ty) = pk.ok_or_else(|| {
+            syn::Error::new(
+                content.span(),
+                "crud_resource! requires a primary-key field, e.g. `id: Uuid`",
+            )
+        })?;
+
+        Ok(CrudResource {
+            table,
+            model,
+            pk_name,
+            pk_ty,
+            scope,
+            create_ty: create_ty.ok_or_else(|| {
+                syn::Error::new(content.span(), "crud_resource! requires a `create:` field")
+            })?,
+            update_ty: update_ty.ok_or_else(|| {
+                syn::Error::new(content.span(), "crud_resource! requires an `update:` field")
+            })?,
+            soft_delete,
+        })
+    }
+}
+
+#[proc_macro]
+pub fn crud_resource(input: TokenStream) -> TokenStream {
+    let spec = parse_macro_input!(input as CrudResource);
+
+    let table = &spec.table;
+    let model = &spec.model;
+    let pk_name = &spec.pk_name;
+    let pk_ty = &spec.pk_ty;
+    let create_ty = &spec.create_ty;
+    let update_ty = &spec.update_ty;
+
+    let create_fn = format_ident!("create_{}", table);
+    let get_fn = format_ident!("get_{}", table);
+    let list_fn = format_ident!("list_{}", table);
+    let update_fn = format_ident!("update_{}", table);
+    let remove_fn = format_ident!("remove_{}", table);
+    let routes_fn = format_ident!("{}_routes", table);
+    let list_query_ty = format_ident!("__{}_list_query", table);
+
+    let scope_path = spec
+        .scope
+        .as_ref()
+        .map(|lit| lit.value())
+        .unwrap_or_else(|| format!("/{}", table));
+
+    // Serial/integer primary keys are DB-generated and come back via
+    // `RETURNING`; UUID primary keys are generated here since Postgres has
+    // no column default that would produce one on its own.
+    let is_uuid_pk = quote!(#pk_ty).to_string() == "Uuid";
+    let create_values = if is_uuid_pk {
+        quote! { (#pk_name.eq(uuid::Uuid::new_v4()), &item) }
+    } else {
+        quote! { &item }
+    };
+
+    // Mirrors the hand-written `list_posts`/`list_all_posts` convention:
+    // a resource with a declared soft-delete flag always hides deleted rows
+    // from its listing route, so `/articles/list` filters the same rows as
+    // `/post/list_all_posts`.
+    let list_query_base = if let Some(soft) = &spec.soft_delete {
+        let flag = &soft.flag_column;
+        quote! { #table.filter(#flag.eq(false)) }
+    } else {
+        quote! { #table }
+    };
+
+    let remove_body = if let Some(soft) = &spec.soft_delete {
+        let flag = &soft.flag_column;
+        let stamp = &soft.timestamp_column;
+        quote! {
+            web::block(move || {
+                let mut conn = pool.get()?;
+                diesel::update(#table.filter(#pk_name.eq(item_id)))
+                    .set((#flag.eq(true), #stamp.eq(Some(chrono::Utc::now().naive_utc()))))
+                    .execute(&mut conn)
+                    .map_err(AppError::from)
+            })
+            .await??
+        }
+    } else {
+        quote! {
+            web::block(move || {
+                let mut conn = pool.get()?;
+                diesel::delete(#table.filter(#pk_name.eq(item_id)))
+                    .execute(&mut conn)
+                    .map_err(AppError::from)
+            })
+            .await??
+        }
+    };
+
+    let expanded = quote! {
+        async fn #create_fn(
+            pool: web::Data<DbPool>,
+            item: web::Json<#create_ty>,
+        ) -> Result<HttpResponse, AppError> {
+            let item = item.into_inner();
+            let new_id = web::block(move || {
+                let mut conn = pool.get()?;
+                diesel::insert_into(#table)
+                    .values(#create_values)
+                    .returning(#pk_name)
+                    .get_result::<#pk_ty>(&mut conn)
+                    .map_err(AppError::from)
+            })
+            .await??;
+
+            Ok(HttpResponse::Ok().json(new_id))
+        }
+
+        async fn #get_fn(
+            pool: web::Data<DbPool>,
+            item_id: web::Path<#pk_ty>,
+        ) -> Result<HttpResponse, AppError> {
+            let item_id = item_id.into_inner();
+            let item = web::block(move || {
+                let mut conn = pool.get()?;
+                #table
+                    .filter(#pk_name.eq(item_id))
+                    .first::<#model>(&mut conn)
+                    .map_err(AppError::from)
+            })
+            .await??;
+
+            Ok(HttpResponse::Ok().json(item))
+        }
+
+        // Bounds the generated listing route so `/list` can't be used to
+        // pull the whole table into memory in one request: `limit` defaults
+        // to 50 and is clamped to 1..=200, a negative `offset` becomes 0.
+        // Out-of-range values are clamped rather than rejected with a 400.
+        #[derive(serde::Deserialize)]
+        struct #list_query_ty {
+            limit: Option<i64>,
+            offset: Option<i64>,
+        }
+
+        async fn #list_fn(
+            pool: web::Data<DbPool>,
+            list_query: web::Query<#list_query_ty>,
+        ) -> Result<HttpResponse, AppError> {
+            let limit = list_query.limit.unwrap_or(50).clamp(1, 200);
+            let offset = list_query.offset.unwrap_or(0).max(0);
+
+            let items = web::block(move || {
+                let mut conn = pool.get()?;
+                #list_query_base
+                    .limit(limit)
+                    .offset(offset)
+                    .load::<#model>(&mut conn)
+                    .map_err(AppError::from)
+            })
+            .await??;
+
+            Ok(HttpResponse::Ok().json(items))
+        }
+
+        async fn #update_fn(
+            pool: web::Data<DbPool>,
+            item_id: web::Path<#pk_ty>,
+            item: web::Json<#update_ty>,
+        ) -> Result<HttpResponse, AppError> {
+            let item_id = item_id.into_inner();
+            let item = item.into_inner();
+
+            let updated = web::block(move || {
+                let mut conn = pool.get()?;
+                diesel::update(#table.filter(#pk_name.eq(item_id)))
+                    .set(&item)
+                    .execute(&mut conn)
+                    .map_err(AppError::from)
+            })
+            .await??;
+
+            if updated > 0 {
+                Ok(HttpResponse::Ok().finish())
+            } else {
+                Err(AppError::NotFound)
+            }
+        }
+
+        async fn #remove_fn(
+            pool: web::Data<DbPool>,
+            item_id: web::Path<#pk_ty>,
+        ) -> Result<HttpResponse, AppError> {
+            let item_id = item_id.into_inner();
+            let affected = #remove_body;
+
+            if affected > 0 {
+                Ok(HttpResponse::Ok().finish())
+            } else {
+                Err(AppError::NotFound)
+            }
+        }
+
+        pub fn #routes_fn(cfg: &mut web::ServiceConfig) {
+            cfg.service(
+                web::scope(#scope_path)
+                    .route("/create", web::post().to(#create_fn))
+                    .route("/get/{id}", web::get().to(#get_fn))
+                    .route("/list", web::get().to(#list_fn))
+                    .route("/update/{id}", web::put().to(#update_fn))
+                    .route("/remove/{id}", web::delete().to(#remove_fn)),
+            );
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -0,0 +1,22 @@
+//! Proof that `crud_resource!` also covers the integer-keyed case: events
+//! has no soft-delete column, so `remove_events` compiles down to a hard
+//! `DELETE`, and the generated `create_events` relies on Postgres's own
+//! serial default plus `RETURNING` rather than a client-generated id.
+use crate::errors::AppError;
+use crate::models::events::{Event, NewEvent};
+use crate::schema::events::dsl::*;
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use diesel::r2d2::{self, ConnectionManager};
+use diesel::PgConnection;
+
+type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
+
+crud_macros::crud_resource! {
+    events => Event {
+        id: i32,
+        scope: "/api/events",
+        create: NewEvent,
+        update: Event,
+    }
+}
@@ -0,0 +1,76 @@
+use actix_web::{error::BlockingError, http::StatusCode, HttpResponse, ResponseError};
+use diesel::r2d2::PoolError;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+#[derive(Debug)]
+pub enum AppError {
+    NotFound,
+    Conflict(String),
+    Validation(String),
+    Pool(PoolError),
+    Database(DieselError),
+    Blocking(String),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NotFound => write!(f, "resource not found"),
+            AppError::Conflict(msg) => write!(f, "{}", msg),
+            AppError::Validation(msg) => write!(f, "{}", msg),
+            AppError::Pool(err) => write!(f, "failed to check out db connection: {}", err),
+            AppError::Database(err) => write!(f, "database error: {}", err),
+            AppError::Blocking(msg) => write!(f, "blocking task failed: {}", msg),
+        }
+    }
+}
+
+impl From<PoolError> for AppError {
+    fn from(err: PoolError) -> Self {
+        AppError::Pool(err)
+    }
+}
+
+impl From<DieselError> for AppError {
+    fn from(err: DieselError) -> Self {
+        match err {
+            DieselError::NotFound => AppError::NotFound,
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                AppError::Conflict(info.message().to_string())
+            }
+            other => AppError::Database(other),
+        }
+    }
+}
+
+impl From<BlockingError> for AppError {
+    fn from(err: BlockingError) -> Self {
+        AppError::Blocking(err.to_string())
+    }
+}
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::NotFound => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::Pool(_) | AppError::Database(_) | AppError::Blocking(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(ErrorBody {
+            error: self.to_string(),
+        })
+    }
+}
@@ -0,0 +1,21 @@
+use crate::schema::events;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Queryable, Insertable, AsChangeset, Serialize, Deserialize)]
+#[table_name = "events"]
+pub struct Event {
+    pub id: i32,
+    pub title: String,
+    pub description: String,
+    pub starts_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Deserialize)]
+#[table_name = "events"]
+pub struct NewEvent {
+    pub title: String,
+    pub description: String,
+    pub starts_at: NaiveDateTime,
+}